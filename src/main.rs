@@ -1,17 +1,31 @@
 use bevy::prelude::*;
 use rand::Rng;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::mem::swap;
 
+mod sound;
+
 // Constants
 const OBJECT_SIZE: f32 = 32.0;
 const WINDOW_WIDTH: f32 = 16.0 * OBJECT_SIZE;
 const WINDOW_HEIGHT: f32 = 16.0 * OBJECT_SIZE;
 const SNAKE_SPEED: f32 = OBJECT_SIZE * 1.;
+// Caps how far input can race ahead of the `FixedUpdate` tick that commits it.
+const INPUT_QUEUE_CAPACITY: usize = 2;
+
+// Step-interval tuning: each orb pickup shortens the tick by this fraction,
+// down to `MIN_STEP_SECONDS`, so the snake accelerates as it grows.
+const INITIAL_STEP_SECONDS: f32 = 0.25;
+const MIN_STEP_SECONDS: f32 = 0.08;
+const STEP_SPEEDUP_FACTOR: f32 = 0.95;
+
+const GRID_WIDTH: i32 = (WINDOW_WIDTH / OBJECT_SIZE) as i32;
+const GRID_HEIGHT: i32 = (WINDOW_HEIGHT / OBJECT_SIZE) as i32;
 
 const TITLE: &str = "Snake";
 
 /// Structs and Components
-#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
 enum Direction {
     #[default]
     Up,
@@ -49,17 +63,33 @@ impl Direction {
 #[derive(Component)]
 struct SnakeHead {
     direction: Direction,
+    queued_directions: VecDeque<Direction>,
 }
 
 impl SnakeHead {
-    fn turn(&mut self, new_direction: Direction) {
-        if !self.direction.is_opposite(&new_direction) {
-            self.turn_unchecked(new_direction)
+    fn turn_unchecked(&mut self, new_direction: Direction) {
+        self.direction = new_direction;
+    }
+
+    /// Queues a direction for a future tick instead of committing it immediately,
+    /// so several key presses between ticks can't both pass the opposite-direction
+    /// check against the same committed direction.
+    fn queue_turn(&mut self, new_direction: Direction) {
+        let last_queued = self.queued_directions.back().unwrap_or(&self.direction);
+        if last_queued.is_opposite(&new_direction) {
+            return;
+        }
+        if self.queued_directions.len() >= INPUT_QUEUE_CAPACITY {
+            return;
         }
+        self.queued_directions.push_back(new_direction);
     }
 
-    fn turn_unchecked(&mut self, new_direction: Direction) {
-        self.direction = new_direction;
+    /// Commits the next queued direction, if any. Called once per `FixedUpdate` tick.
+    fn commit_queued_turn(&mut self) {
+        if let Some(direction) = self.queued_directions.pop_front() {
+            self.turn_unchecked(direction);
+        }
     }
 }
 
@@ -69,6 +99,131 @@ struct SnakeBody;
 #[derive(Component)]
 struct Orb;
 
+/// Tracks the current `FixedUpdate` step interval and shortens it on each orb
+/// pickup, floored at `MIN_STEP_SECONDS`, so the snake speeds up as it grows.
+#[derive(Resource)]
+struct GameSpeed {
+    step_seconds: f32,
+}
+
+impl Default for GameSpeed {
+    fn default() -> Self {
+        Self {
+            step_seconds: INITIAL_STEP_SECONDS,
+        }
+    }
+}
+
+impl GameSpeed {
+    fn speed_up(&mut self) {
+        self.step_seconds = (self.step_seconds * STEP_SPEEDUP_FACTOR).max(MIN_STEP_SECONDS);
+    }
+}
+
+/// Whether `SnakeHead` is steered by the player or by `ai_steer`.
+#[derive(Resource, Default, PartialEq, Eq)]
+enum ControlMode {
+    #[default]
+    Human,
+    Ai,
+}
+
+/// Top-level lifecycle of the game, gating which systems run.
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+enum GameState {
+    #[default]
+    Menu,
+    Playing,
+    Paused,
+    GameOver,
+}
+
+/// The snake's length at the moment it crashed, shown on the game-over screen.
+#[derive(Resource, Default)]
+struct Score {
+    length: usize,
+}
+
+#[derive(Component)]
+struct MenuOverlay;
+
+#[derive(Component)]
+struct GameOverOverlay;
+
+/// Fired when the snake eats an orb, carrying the pickup point (for spatial
+/// audio) and the snake's new length (for the TTS announcement).
+#[derive(Event)]
+struct OrbEaten {
+    translation: Vec3,
+    length: usize,
+}
+
+/// Fired when the head collides with the body, carrying the crash point.
+#[derive(Event)]
+struct SnakeCrashed {
+    translation: Vec3,
+}
+
+/// Converts a world-space translation to a grid cell, inverting the offset
+/// applied by `get_random_position`.
+fn to_cell(translation: Vec3) -> (i32, i32) {
+    let x = (translation.x / OBJECT_SIZE).round() as i32 + GRID_WIDTH / 2;
+    let y = (translation.y / OBJECT_SIZE).round() as i32 + GRID_HEIGHT / 2;
+    (x.rem_euclid(GRID_WIDTH), y.rem_euclid(GRID_HEIGHT))
+}
+
+/// The four cardinal neighbors of `cell`, wrapping at the grid edges just
+/// like `Moveable::step` wraps the snake's translation.
+fn neighbors(cell: (i32, i32)) -> [(Direction, (i32, i32)); 4] {
+    let (x, y) = cell;
+    [
+        (Direction::Up, (x, (y + 1).rem_euclid(GRID_HEIGHT))),
+        (Direction::Down, (x, (y - 1).rem_euclid(GRID_HEIGHT))),
+        (Direction::Left, ((x - 1).rem_euclid(GRID_WIDTH), y)),
+        (Direction::Right, ((x + 1).rem_euclid(GRID_WIDTH), y)),
+    ]
+}
+
+/// Breadth-first search from `start` to `goal` over the grid, treating
+/// `blocked` cells as impassable. Returns the first step to take, if a path
+/// exists.
+fn bfs_first_step(
+    start: (i32, i32),
+    goal: (i32, i32),
+    blocked: &HashSet<(i32, i32)>,
+) -> Option<Direction> {
+    if start == goal {
+        return None;
+    }
+
+    let mut came_from: HashMap<(i32, i32), ((i32, i32), Direction)> = HashMap::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+
+    while let Some(current) = queue.pop_front() {
+        if current == goal {
+            let mut cell = goal;
+            let mut first_step_direction = came_from[&cell].1.clone();
+            while came_from[&cell].0 != start {
+                cell = came_from[&cell].0;
+                first_step_direction = came_from[&cell].1.clone();
+            }
+            return Some(first_step_direction);
+        }
+
+        for (direction, neighbor) in neighbors(current) {
+            let already_seen = came_from.contains_key(&neighbor) || neighbor == start;
+            if blocked.contains(&neighbor) || already_seen {
+                continue;
+            }
+            came_from.insert(neighbor, (current, direction));
+            queue.push_back(neighbor);
+        }
+    }
+
+    None
+}
+
 trait Moveable {
     fn step(&mut self);
     fn clone_translation(&self) -> Vec3;
@@ -122,30 +277,53 @@ fn main() {
             }),
             ..default()
         }))
+        .init_state::<GameState>()
+        .init_resource::<Score>()
+        .add_event::<OrbEaten>()
+        .add_event::<SnakeCrashed>()
+        .add_plugins(sound::SoundPlugin)
         .add_systems(Startup, setup)
-        .add_systems(FixedUpdate, (move_snake, check_collisions))
-        .insert_resource(Time::<Fixed>::from_seconds(0.25))
-        .add_systems(Update, handle_input)
+        .add_systems(
+            FixedUpdate,
+            (ai_steer, move_snake, check_collisions).run_if(in_state(GameState::Playing)),
+        )
+        .insert_resource(Time::<Fixed>::from_seconds(INITIAL_STEP_SECONDS as f64))
+        .init_resource::<GameSpeed>()
+        .init_resource::<ControlMode>()
+        .add_systems(
+            Update,
+            (
+                handle_input.run_if(in_state(GameState::Playing)),
+                toggle_pause,
+            ),
+        )
+        .add_systems(OnEnter(GameState::Menu), show_menu)
+        .add_systems(OnExit(GameState::Menu), despawn_with::<MenuOverlay>)
+        .add_systems(Update, start_game.run_if(in_state(GameState::Menu)))
+        .add_systems(OnEnter(GameState::GameOver), show_game_over)
+        .add_systems(OnExit(GameState::GameOver), despawn_with::<GameOverOverlay>)
+        .add_systems(Update, restart_game.run_if(in_state(GameState::GameOver)))
         .run();
 }
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn(Camera2d);
+fn setup(mut commands: Commands) {
+    commands.spawn((Camera2d, SpatialListener::new(OBJECT_SIZE)));
+}
 
+/// Spawns the snake head, its starting body segment, and the orb. Called both
+/// when leaving the menu and when restarting after a game over.
+fn spawn_game(commands: &mut Commands, asset_server: &Res<AssetServer>) {
     // Spawns snake head
     commands.spawn((
         Sprite::from_image(asset_server.load("textures/head.png")),
         SnakeHead {
             direction: Direction::Up,
+            queued_directions: VecDeque::new(),
         },
     ));
 
     // Spawns snake body
-    spawn_snake_body(
-        &mut commands,
-        &asset_server,
-        Vec3::new(0., -OBJECT_SIZE, 0.),
-    );
+    spawn_snake_body(commands, asset_server, Vec3::new(0., -OBJECT_SIZE, 0.));
 
     // Spawns orb
     commands.spawn((
@@ -155,9 +333,100 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     ));
 }
 
+/// Despawns every entity with component `T`. Used to tear down menu and
+/// game-over overlays on state exit.
+fn despawn_with<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
+    query.iter().for_each(|entity| {
+        commands.entity(entity).despawn();
+    });
+}
+
+fn show_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Text2d::new("Press Enter to start\n(Tab toggles AI autopilot, Escape pauses)"),
+        TextFont {
+            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+            ..default()
+        },
+        MenuOverlay,
+    ));
+}
+
+fn start_game(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Enter) {
+        spawn_game(&mut commands, &asset_server);
+        next_state.set(GameState::Playing);
+    }
+}
+
+fn show_game_over(mut commands: Commands, asset_server: Res<AssetServer>, score: Res<Score>) {
+    commands.spawn((
+        Text2d::new(format!(
+            "Game over! Length: {}\nPress Enter to play again",
+            score.length
+        )),
+        TextFont {
+            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+            ..default()
+        },
+        GameOverOverlay,
+    ));
+}
+
+fn restart_game(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut game_speed: ResMut<GameSpeed>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    leftover_entities: Query<Entity, Or<(With<SnakeHead>, With<SnakeBody>, With<Orb>)>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Enter) {
+        return;
+    }
+
+    leftover_entities.iter().for_each(|entity| {
+        commands.entity(entity).despawn();
+    });
+
+    *game_speed = GameSpeed::default();
+    *fixed_time = Time::<Fixed>::from_seconds(game_speed.step_seconds as f64);
+
+    spawn_game(&mut commands, &asset_server);
+    next_state.set(GameState::Playing);
+}
+
+fn toggle_pause(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Escape) {
+        return;
+    }
+
+    match state.get() {
+        GameState::Playing => next_state.set(GameState::Paused),
+        GameState::Paused => next_state.set(GameState::Playing),
+        _ => {}
+    }
+}
+
 fn check_collisions(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    mut game_speed: ResMut<GameSpeed>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    mut score: ResMut<Score>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut orb_eaten_events: EventWriter<OrbEaten>,
+    mut crashed_events: EventWriter<SnakeCrashed>,
     mut orb_query: Query<&mut Transform, (With<Orb>, Without<SnakeHead>, Without<SnakeBody>)>,
     mut snake_head_query: Query<(&mut Transform, &mut SnakeHead, &mut Sprite)>,
     snake_body_query: Query<(&mut Transform, Entity), (With<SnakeBody>, Without<SnakeHead>)>,
@@ -174,18 +443,33 @@ fn check_collisions(
     }
 
     if crashed {
+        score.length = snake_body_query.iter().count() + 1;
         snake_body_query.iter().for_each(|(_, body)| {
             commands.entity(body).despawn();
         });
         snake_head_moveable.2.image = asset_server.load("textures/head_2.png");
+        crashed_events.send(SnakeCrashed {
+            translation: *snake_head_translation,
+        });
+        next_state.set(GameState::GameOver);
+        return;
     }
 
     if orb_translation == snake_head_translation {
         let orb_position = get_random_position();
+        let new_length = snake_body_query.iter().count() + 2;
 
         orb_query.single_mut().unwrap().translation = orb_position;
 
         spawn_snake_body(&mut commands, &asset_server, *snake_head_translation);
+
+        game_speed.speed_up();
+        *fixed_time = Time::<Fixed>::from_seconds(game_speed.step_seconds as f64);
+
+        orb_eaten_events.send(OrbEaten {
+            translation: *snake_head_translation,
+            length: new_length,
+        });
     }
 }
 
@@ -203,6 +487,8 @@ fn move_snake(
 ) {
     let mut snake_moveable = snake_head_query.single_mut().unwrap();
 
+    snake_moveable.1.commit_queued_turn();
+
     let mut last_translation = snake_moveable.clone_translation();
 
     snake_moveable.step();
@@ -214,17 +500,60 @@ fn move_snake(
 
 fn handle_input(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut control_mode: ResMut<ControlMode>,
     mut snake_head_query: Query<(&mut Transform, &mut SnakeHead)>,
 ) {
-    keyboard_input.get_pressed().for_each(|key| {
+    if keyboard_input.just_pressed(KeyCode::Tab) {
+        *control_mode = match *control_mode {
+            ControlMode::Human => ControlMode::Ai,
+            ControlMode::Ai => ControlMode::Human,
+        };
+    }
+
+    if *control_mode != ControlMode::Human {
+        return;
+    }
+
+    keyboard_input.get_just_pressed().for_each(|key| {
         snake_head_query.iter_mut().for_each(|(_, mut snake_head)| {
             if let Ok(direction) = Direction::try_from(key) {
-                snake_head.turn(direction);
+                snake_head.queue_turn(direction);
             }
         });
     });
 }
 
+fn ai_steer(
+    control_mode: Res<ControlMode>,
+    mut snake_head_query: Query<(&Transform, &mut SnakeHead)>,
+    snake_body_query: Query<&Transform, (With<SnakeBody>, Without<SnakeHead>)>,
+    orb_query: Query<&Transform, (With<Orb>, Without<SnakeHead>, Without<SnakeBody>)>,
+) {
+    if *control_mode != ControlMode::Ai {
+        return;
+    }
+
+    let (head_transform, mut snake_head) = snake_head_query.single_mut().unwrap();
+    let head_cell = to_cell(head_transform.translation);
+    let orb_cell = to_cell(orb_query.single().unwrap().translation);
+
+    let blocked: HashSet<(i32, i32)> = snake_body_query
+        .iter()
+        .map(|transform| to_cell(transform.translation))
+        .collect();
+
+    let direction = bfs_first_step(head_cell, orb_cell, &blocked).or_else(|| {
+        neighbors(head_cell)
+            .into_iter()
+            .find(|(_, cell)| !blocked.contains(cell))
+            .map(|(direction, _)| direction)
+    });
+
+    if let Some(direction) = direction {
+        snake_head.queue_turn(direction);
+    }
+}
+
 fn get_random_position() -> Vec3 {
     let mut rng = rand::rng();
     let x = (rng.random_range(0..(WINDOW_WIDTH / OBJECT_SIZE) as i32)
@@ -235,3 +564,136 @@ fn get_random_position() -> Vec3 {
         * OBJECT_SIZE;
     Vec3::new(x, y, 0.)
 }
+
+#[cfg(test)]
+mod ai_steer_tests {
+    use super::*;
+
+    #[test]
+    fn neighbors_wrap_at_grid_edges() {
+        let top_right = (GRID_WIDTH - 1, GRID_HEIGHT - 1);
+        let wrapped: HashMap<Direction, (i32, i32)> = neighbors(top_right).into_iter().collect();
+
+        assert_eq!(wrapped[&Direction::Up], (GRID_WIDTH - 1, 0));
+        assert_eq!(wrapped[&Direction::Right], (0, GRID_HEIGHT - 1));
+        assert_eq!(wrapped[&Direction::Down], (GRID_WIDTH - 1, GRID_HEIGHT - 2));
+        assert_eq!(wrapped[&Direction::Left], (GRID_WIDTH - 2, GRID_HEIGHT - 1));
+    }
+
+    #[test]
+    fn to_cell_round_trips_get_random_position_offset() {
+        // The center of the screen sits at grid cell (GRID_WIDTH / 2, GRID_HEIGHT / 2).
+        assert_eq!(to_cell(Vec3::ZERO), (GRID_WIDTH / 2, GRID_HEIGHT / 2));
+    }
+
+    #[test]
+    fn bfs_finds_shortest_unblocked_path() {
+        let blocked = HashSet::new();
+        let direction = bfs_first_step((0, 0), (2, 0), &blocked).unwrap();
+        assert_eq!(direction, Direction::Right);
+    }
+
+    #[test]
+    fn bfs_never_steps_into_a_blocked_cell() {
+        // Block every cell except a single corridor straight up from the start.
+        let mut blocked = HashSet::new();
+        for x in 0..GRID_WIDTH {
+            for y in 0..GRID_HEIGHT {
+                if x != 0 {
+                    blocked.insert((x, y));
+                }
+            }
+        }
+
+        let start = (0, 0);
+        let goal = (0, 5);
+        let direction = bfs_first_step(start, goal, &blocked).unwrap();
+        let (_, next_cell) = neighbors(start)
+            .into_iter()
+            .find(|(candidate, _)| *candidate == direction)
+            .unwrap();
+
+        assert!(!blocked.contains(&next_cell));
+        assert_eq!(direction, Direction::Up);
+    }
+
+    #[test]
+    fn bfs_returns_none_when_start_equals_goal() {
+        let blocked = HashSet::new();
+        assert_eq!(bfs_first_step((3, 3), (3, 3), &blocked), None);
+    }
+
+    #[test]
+    fn bfs_returns_none_when_goal_is_unreachable() {
+        // Surround the goal on all four sides so no path can reach it.
+        let goal = (5, 5);
+        let mut blocked = HashSet::new();
+        for (_, cell) in neighbors(goal) {
+            blocked.insert(cell);
+        }
+
+        assert_eq!(bfs_first_step((0, 0), goal, &blocked), None);
+    }
+}
+
+#[cfg(test)]
+mod input_queue_tests {
+    use super::*;
+
+    fn new_snake_head(direction: Direction) -> SnakeHead {
+        SnakeHead {
+            direction,
+            queued_directions: VecDeque::new(),
+        }
+    }
+
+    #[test]
+    fn queue_turn_rejects_an_immediate_reversal() {
+        let mut snake_head = new_snake_head(Direction::Up);
+        snake_head.queue_turn(Direction::Down);
+        assert!(snake_head.queued_directions.is_empty());
+    }
+
+    #[test]
+    fn queue_turn_never_commits_a_reversal_across_several_quick_presses() {
+        let mut snake_head = new_snake_head(Direction::Up);
+
+        // Several key presses land in the same tick, before any commit_queued_turn.
+        snake_head.queue_turn(Direction::Left);
+        snake_head.queue_turn(Direction::Down);
+
+        let mut committed = vec![snake_head.direction.clone()];
+        while !snake_head.queued_directions.is_empty() {
+            snake_head.commit_queued_turn();
+            committed.push(snake_head.direction.clone());
+        }
+
+        assert!(committed
+            .windows(2)
+            .all(|pair| !pair[0].is_opposite(&pair[1])));
+    }
+}
+
+#[cfg(test)]
+mod game_speed_tests {
+    use super::*;
+
+    #[test]
+    fn speed_up_shortens_the_step_by_the_speedup_factor() {
+        let mut game_speed = GameSpeed::default();
+        game_speed.speed_up();
+        assert_eq!(
+            game_speed.step_seconds,
+            INITIAL_STEP_SECONDS * STEP_SPEEDUP_FACTOR
+        );
+    }
+
+    #[test]
+    fn speed_up_floors_at_min_step_seconds() {
+        let mut game_speed = GameSpeed::default();
+        for _ in 0..1000 {
+            game_speed.speed_up();
+        }
+        assert_eq!(game_speed.step_seconds, MIN_STEP_SECONDS);
+    }
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use bevy_tts::Tts;
+
+use crate::{GameState, OrbEaten, Score, SnakeCrashed};
+
+/// Positional eat/crash sound effects plus spoken length and game-over
+/// announcements, ported from blackout's `sound.rs` and `bevy_tts` setup.
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (play_eat_sound, play_crash_sound)
+                .after(crate::check_collisions)
+                .run_if(in_state(GameState::Playing)),
+        );
+
+        // `bevy_tts::Tts`'s `Default` impl unwraps the backend it constructs, so
+        // adding the plugin unconditionally would panic at startup on machines
+        // with no screen-reader/TTS backend (headless boxes, most containers,
+        // plenty of ordinary desktops). Probe for a backend first and only wire
+        // up the spoken announcements if one is actually available.
+        if bevy_tts::tts::Tts::default().is_err() {
+            warn!("no text-to-speech backend available; length/game-over announcements disabled");
+            return;
+        }
+
+        app.add_plugins(bevy_tts::TtsPlugin)
+            .add_systems(
+                FixedUpdate,
+                announce_length
+                    .after(crate::check_collisions)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnEnter(GameState::GameOver), announce_game_over);
+    }
+}
+
+fn play_eat_sound(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut orb_eaten_events: EventReader<OrbEaten>,
+) {
+    for event in orb_eaten_events.read() {
+        commands.spawn((
+            AudioPlayer::new(asset_server.load("sounds/eat.ogg")),
+            PlaybackSettings {
+                spatial: true,
+                ..PlaybackSettings::DESPAWN
+            },
+            Transform::from_translation(event.translation),
+        ));
+    }
+}
+
+fn play_crash_sound(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut crashed_events: EventReader<SnakeCrashed>,
+) {
+    for event in crashed_events.read() {
+        commands.spawn((
+            AudioPlayer::new(asset_server.load("sounds/crash.ogg")),
+            PlaybackSettings {
+                spatial: true,
+                ..PlaybackSettings::DESPAWN
+            },
+            Transform::from_translation(event.translation),
+        ));
+    }
+}
+
+fn announce_length(mut tts: ResMut<Tts>, mut orb_eaten_events: EventReader<OrbEaten>) {
+    for event in orb_eaten_events.read() {
+        let _ = tts.speak(format!("Length {}", event.length), true);
+    }
+}
+
+fn announce_game_over(mut tts: ResMut<Tts>, score: Res<Score>) {
+    let _ = tts.speak(format!("Game over. Final length {}", score.length), true);
+}